@@ -1,24 +1,223 @@
 use std::{
+    collections::HashMap,
     ffi::{CStr, CString},
-    os::raw::c_char,
-    time::Duration,
+    os::raw::{c_char, c_void},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
 use eventdbx_client::{
     AggregateSort, AggregateSortField, AppendEventRequest, ClientConfig, CreateAggregateRequest,
-    EventDbxClient, ListAggregatesOptions, ListEventsOptions, PatchEventRequest, PublishTarget,
-    SelectAggregateRequest, SetAggregateArchiveRequest,
+    EventDbxClient, EventInclusionProof, FollowEventsFilter, ListAggregatesOptions,
+    ListEventsOptions, PatchEventRequest, PublishTarget, SelectAggregateRequest,
+    SetAggregateArchiveRequest,
 };
+use futures_util::StreamExt;
 use serde::Deserialize;
 use serde_json::{Map, Value};
-use tokio::runtime::Runtime;
+use sha2::{Digest, Sha256};
+use tokio::runtime::{Handle as RuntimeHandle, Runtime};
+use tokio::task::JoinHandle;
 
-struct DbxHandle {
+struct DbxClientInner {
     runtime: Runtime,
     client: EventDbxClient,
+    stats: Mutex<HashMap<&'static str, OperationMetric>>,
+}
+
+/// Opaque handle returned to callers. The runtime/client/stats live behind an
+/// `Arc` so a `DbxCursor` can hold its own clone: a cursor opened before
+/// `dbx_client_free` keeps working (and keeps the connection alive) until the
+/// cursor itself is freed, rather than dereferencing memory the free call
+/// already dropped.
+struct DbxHandle {
+    inner: Arc<DbxClientInner>,
+}
+
+impl std::ops::Deref for DbxHandle {
+    type Target = DbxClientInner;
+
+    fn deref(&self) -> &DbxClientInner {
+        &self.inner
+    }
+}
+
+/// Upper bound (in microseconds) of each latency bucket, used to approximate
+/// percentiles without keeping every sample around.
+const LATENCY_BUCKETS_US: [u64; 8] = [
+    1_000,
+    5_000,
+    10_000,
+    50_000,
+    100_000,
+    500_000,
+    1_000_000,
+    u64::MAX,
+];
+
+#[derive(Default)]
+struct OperationMetric {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    total_micros: AtomicU64,
+    max_micros: AtomicU64,
+    buckets: [AtomicU64; LATENCY_BUCKETS_US.len()],
+}
+
+impl OperationMetric {
+    fn record(&self, elapsed: Duration, is_err: bool) {
+        let micros = elapsed.as_micros().min(u128::from(u64::MAX)) as u64;
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if is_err {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+        let bucket = LATENCY_BUCKETS_US
+            .iter()
+            .position(|&bound| micros <= bound)
+            .unwrap_or(LATENCY_BUCKETS_US.len() - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn percentile_ms(&self, fraction: f64) -> f64 {
+        let total: u64 = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let target = ((total as f64) * fraction).ceil() as u64;
+        let mut seen = 0u64;
+        let overflow_bucket = LATENCY_BUCKETS_US.len() - 1;
+        for (idx, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket.load(Ordering::Relaxed);
+            if seen >= target.max(1) {
+                if idx == overflow_bucket {
+                    return self.max_micros.load(Ordering::Relaxed) as f64 / 1000.0;
+                }
+                return LATENCY_BUCKETS_US[idx] as f64 / 1000.0;
+            }
+        }
+        self.max_micros.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    fn to_json(&self) -> Value {
+        let calls = self.calls.load(Ordering::Relaxed);
+        let errors = self.errors.load(Ordering::Relaxed);
+        let total_micros = self.total_micros.load(Ordering::Relaxed);
+        let max_ms = self.max_micros.load(Ordering::Relaxed) as f64 / 1000.0;
+        let avg_ms = if calls > 0 {
+            (total_micros as f64 / calls as f64) / 1000.0
+        } else {
+            0.0
+        };
+        Value::Object(
+            [
+                ("calls".to_string(), Value::from(calls)),
+                ("errors".to_string(), Value::from(errors)),
+                (
+                    "latencyMs".to_string(),
+                    Value::Object(
+                        [
+                            ("avg".to_string(), Value::from(avg_ms)),
+                            ("p50".to_string(), Value::from(self.percentile_ms(0.5))),
+                            ("p95".to_string(), Value::from(self.percentile_ms(0.95))),
+                            ("max".to_string(), Value::from(max_ms)),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+/// Times `op`, recording a call/error/latency sample under `operation` in
+/// `handle`'s stats before returning `op`'s result unchanged.
+fn timed_operation<T, E>(
+    handle: &DbxHandle,
+    operation: &'static str,
+    op: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = op();
+    let mut stats = handle.stats.lock().unwrap_or_else(|e| e.into_inner());
+    stats
+        .entry(operation)
+        .or_default()
+        .record(started.elapsed(), result.is_err());
+    result
+}
+
+/// Wraps a raw C callback + user-data pointer so it can be moved into the
+/// spawned subscription task. The caller guarantees `callback` stays valid
+/// and `user_data` stays valid for as long as the subscription is open.
+struct SubscriptionCallback {
+    callback: extern "C" fn(*const c_char, *mut c_void),
+    user_data: *mut c_void,
+}
+
+unsafe impl Send for SubscriptionCallback {}
+
+/// Holds its own `Arc` clone of the handle's inner state (the same guard
+/// `DbxCursor` uses) so a `dbx_client_free` call while this subscription is
+/// still open cannot drop the `Runtime` out from under the background task
+/// or `dbx_unsubscribe`'s own `block_on` join.
+struct DbxSubscription {
+    inner: Arc<DbxClientInner>,
+    cancelled: Arc<AtomicBool>,
+    runtime_handle: RuntimeHandle,
+    task: Option<JoinHandle<()>>,
+}
+
+/// A resumable, reconnecting pull-style subscription on one aggregate.
+/// `dbx_subscribe_next` blocks on `receiver` so the caller drives the pace;
+/// a background task on the handle's runtime keeps the connection alive,
+/// reconnecting from the last delivered cursor with capped backoff. Holds its
+/// own `Arc` clone of the handle's inner state, the same guard `DbxCursor`
+/// and `DbxSubscription` use, so a `dbx_client_free` call while the stream is
+/// still open cannot drop the `Runtime` out from under the background task
+/// or `dbx_subscribe_close`'s own `block_on` join.
+struct DbxSubscribeStream {
+    inner: Arc<DbxClientInner>,
+    receiver: std::sync::mpsc::Receiver<Value>,
+    cancelled: Arc<AtomicBool>,
+    runtime_handle: RuntimeHandle,
+    task: Option<JoinHandle<()>>,
+}
+
+enum CursorQuery {
+    Events {
+        aggregate_type: String,
+        aggregate_id: String,
+        options: ListEventsOptions,
+    },
+    Aggregates {
+        options: ListAggregatesOptions,
+    },
+}
+
+/// Walks a full `list_events`/`list_aggregates` range page by page, re-issuing
+/// the frozen filter/sort/take options with the last-seen cursor so callers
+/// don't have to track pagination state themselves.
+///
+/// Holds its own `Arc` clone of the handle's inner state (rather than the raw
+/// `*mut DbxHandle` it was opened from) so a `dbx_client_free` call on the
+/// original handle while this cursor is still open cannot leave `next`/`free`
+/// dereferencing freed memory; the underlying runtime/client is only dropped
+/// once every cursor referencing it has also been freed.
+struct DbxCursor {
+    inner: Arc<DbxClientInner>,
+    query: CursorQuery,
+    exhausted: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 struct ConfigInput {
     ip: Option<String>,
@@ -32,6 +231,53 @@ struct ConfigInput {
     connect_timeout_ms: Option<u64>,
     request_timeout_ms: Option<u64>,
     protocol_version: Option<u16>,
+    #[serde(default)]
+    profiles: std::collections::HashMap<String, ConfigInput>,
+    active_profile: Option<String>,
+}
+
+impl ConfigInput {
+    /// Layers `other`'s set fields over `self`, leaving fields `other` doesn't
+    /// set untouched. Used to apply a named profile on top of the base config.
+    fn overlay(self, other: &ConfigInput) -> ConfigInput {
+        ConfigInput {
+            ip: other.ip.clone().or(self.ip),
+            host: other.host.clone().or(self.host),
+            port: other.port.or(self.port),
+            token: other.token.clone().or(self.token),
+            tenant_id: other.tenant_id.clone().or(self.tenant_id),
+            tenant: other.tenant.clone().or(self.tenant),
+            tenant_id_env: other.tenant_id_env.clone().or(self.tenant_id_env),
+            no_noise: other.no_noise.or(self.no_noise),
+            connect_timeout_ms: other.connect_timeout_ms.or(self.connect_timeout_ms),
+            request_timeout_ms: other.request_timeout_ms.or(self.request_timeout_ms),
+            protocol_version: other.protocol_version.or(self.protocol_version),
+            profiles: self.profiles,
+            active_profile: self.active_profile,
+        }
+    }
+}
+
+/// Resolves the active named profile (from `cfg.activeProfile` or the
+/// `EVENTDBX_PROFILE` env var, in that order) and layers it over `cfg`'s base
+/// fields, leaving `cfg` unchanged when no profile is selected.
+fn resolve_profile(cfg: ConfigInput) -> Result<ConfigInput, String> {
+    let active = cfg
+        .active_profile
+        .clone()
+        .or_else(|| std::env::var("EVENTDBX_PROFILE").ok());
+
+    let Some(name) = active else {
+        return Ok(cfg);
+    };
+
+    let profile = cfg
+        .profiles
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("unknown profile: {name}"))?;
+
+    Ok(cfg.overlay(&profile))
 }
 
 fn default_host(cfg: &ConfigInput) -> String {
@@ -107,13 +353,15 @@ mod tests {
 
     #[test]
     fn payload_options_defaults_to_empty_payload() {
-        let (payload, note, metadata, token, publish_targets) =
+        let (payload, note, metadata, token, publish_targets, expected_version, causal_context) =
             parse_payload_options(Value::Object(Map::new()));
         assert_eq!(payload, Value::Null);
         assert!(note.is_none());
         assert!(metadata.is_none());
         assert!(token.is_none());
         assert!(publish_targets.is_empty());
+        assert!(expected_version.is_none());
+        assert!(causal_context.is_none());
     }
 
     #[test]
@@ -123,17 +371,244 @@ mod tests {
             "metadata": { "@source": "test" },
             "note": "demo",
             "token": "abc",
-            "publishTargets": [{ "plugin": "search", "mode": "all" }]
+            "publishTargets": [{ "plugin": "search", "mode": "all" }],
+            "expectedVersion": 7,
+            "causalContext": "ctx-token"
         });
-        let (payload, note, metadata, token, publish_targets) = parse_payload_options(input);
+        let (payload, note, metadata, token, publish_targets, expected_version, causal_context) =
+            parse_payload_options(input);
         assert_eq!(payload, serde_json::json!({ "name": "Ada" }));
         assert_eq!(metadata, Some(serde_json::json!({ "@source": "test" })));
         assert_eq!(note.as_deref(), Some("demo"));
         assert_eq!(token.as_deref(), Some("abc"));
+        assert_eq!(expected_version, Some(7));
+        assert_eq!(causal_context.as_deref(), Some("ctx-token"));
         assert_eq!(publish_targets.len(), 1);
         assert_eq!(publish_targets[0].plugin, "search");
         assert_eq!(publish_targets[0].mode.as_deref(), Some("all"));
     }
+
+    #[test]
+    fn append_event_request_requires_identifying_fields() {
+        let item = serde_json::json!({ "aggregateType": "user", "eventType": "created" });
+        let err = parse_append_event_request(&item).unwrap_err();
+        assert!(err.contains("aggregateId"));
+    }
+
+    #[test]
+    fn append_event_request_parses_full_item() {
+        let item = serde_json::json!({
+            "aggregateType": "user",
+            "aggregateId": "u-1",
+            "eventType": "created",
+            "payload": { "name": "Ada" },
+            "note": "import"
+        });
+        let request = parse_append_event_request(&item).expect("valid item");
+        assert_eq!(request.aggregate_type, "user");
+        assert_eq!(request.aggregate_id, "u-1");
+        assert_eq!(request.event_type, "created");
+        assert_eq!(request.note.as_deref(), Some("import"));
+    }
+
+    #[test]
+    fn follow_filter_reads_from_cursor() {
+        let value = serde_json::json!({ "fromCursor": "cur-42" });
+        let filter = parse_follow_filter(Some(&value));
+        assert_eq!(filter.from_cursor.as_deref(), Some("cur-42"));
+    }
+
+    #[test]
+    fn follow_filter_defaults_when_absent() {
+        let filter = parse_follow_filter(None);
+        assert!(filter.from_cursor.is_none());
+        assert!(filter.filter.is_none());
+    }
+
+    #[test]
+    fn resolve_profile_overlays_selected_profile_over_base() {
+        let input = serde_json::json!({
+            "host": "base.example.com",
+            "tenant": "base-tenant",
+            "activeProfile": "staging",
+            "profiles": {
+                "staging": { "host": "staging.example.com" }
+            }
+        });
+        let cfg: ConfigInput = serde_json::from_value(input).unwrap();
+        let resolved = resolve_profile(cfg).expect("known profile");
+        assert_eq!(resolved.host.as_deref(), Some("staging.example.com"));
+        assert_eq!(resolved.tenant.as_deref(), Some("base-tenant"));
+    }
+
+    #[test]
+    fn resolve_profile_errors_on_unknown_active_profile() {
+        let input = serde_json::json!({ "activeProfile": "missing" });
+        let cfg: ConfigInput = serde_json::from_value(input).unwrap();
+        let err = resolve_profile(cfg).unwrap_err();
+        assert!(err.contains("missing"));
+    }
+
+    #[test]
+    fn resolve_profile_is_a_no_op_without_active_profile() {
+        let input = serde_json::json!({ "host": "only.example.com" });
+        let cfg: ConfigInput = serde_json::from_value(input).unwrap();
+        let resolved = resolve_profile(cfg).expect("no profile selected");
+        assert_eq!(resolved.host.as_deref(), Some("only.example.com"));
+    }
+
+    #[test]
+    fn operation_metric_records_calls_and_errors() {
+        let metric = OperationMetric::default();
+        metric.record(Duration::from_millis(10), false);
+        metric.record(Duration::from_millis(20), true);
+        assert_eq!(metric.calls.load(Ordering::Relaxed), 2);
+        assert_eq!(metric.errors.load(Ordering::Relaxed), 1);
+        assert!(metric.max_micros.load(Ordering::Relaxed) >= 20_000);
+    }
+
+    #[test]
+    fn operation_metric_percentile_reflects_bucket_of_recorded_samples() {
+        let metric = OperationMetric::default();
+        for _ in 0..9 {
+            metric.record(Duration::from_micros(500), false);
+        }
+        metric.record(Duration::from_millis(900), false);
+        assert_eq!(metric.percentile_ms(0.5), 1.0);
+        assert_eq!(metric.percentile_ms(0.95), 1_000.0);
+    }
+
+    #[test]
+    fn operation_metric_percentile_clamps_overflow_bucket_to_max_micros() {
+        let metric = OperationMetric::default();
+        metric.record(Duration::from_millis(1_500), false);
+        assert_eq!(metric.percentile_ms(0.95), 1_500.0);
+    }
+
+    #[test]
+    fn canonical_event_bytes_ignores_key_order() {
+        let a = serde_json::json!({ "b": 1, "a": 2 });
+        let b = serde_json::json!({ "a": 2, "b": 1 });
+        assert_eq!(canonical_event_bytes(&a), canonical_event_bytes(&b));
+    }
+
+    #[test]
+    fn compute_merkle_root_matches_hand_computed_root() {
+        let leaf = serde_json::json!({ "eventType": "created" });
+        let mut leaf_hasher = Sha256::new();
+        leaf_hasher.update([0x00]);
+        leaf_hasher.update(canonical_event_bytes(&leaf));
+        let leaf_hash = leaf_hasher.finalize().to_vec();
+
+        let sibling = vec![7u8; 32];
+        let mut parent_hasher = Sha256::new();
+        parent_hasher.update([0x01]);
+        parent_hasher.update(&leaf_hash);
+        parent_hasher.update(&sibling);
+        let expected_root = hex_encode(&parent_hasher.finalize());
+
+        let proof = vec![ProofStep {
+            sibling,
+            is_right: true,
+        }];
+        assert_eq!(compute_merkle_root(&leaf, &proof), expected_root);
+    }
+
+    #[test]
+    fn parse_inclusion_proof_requires_sibling_and_direction() {
+        let value = serde_json::json!([{ "sibling": "ab" }]);
+        let err = parse_inclusion_proof(&value).unwrap_err();
+        assert!(err.contains("isRight"));
+    }
+
+    #[test]
+    fn patch_event_request_requires_patch_field() {
+        let item = serde_json::json!({
+            "aggregateType": "user",
+            "aggregateId": "u-1",
+            "eventType": "renamed"
+        });
+        let err = parse_patch_event_request(&item).unwrap_err();
+        assert!(err.contains("patch"));
+    }
+
+    #[test]
+    fn parse_version_conflict_extracts_expected_and_found() {
+        let message = "version conflict: expected 3, found 5";
+        assert_eq!(parse_version_conflict(message), Some((3, 5)));
+    }
+
+    #[test]
+    fn parse_version_conflict_ignores_unrelated_errors() {
+        assert_eq!(parse_version_conflict("aggregate not found"), None);
+    }
+
+    #[test]
+    fn parse_version_conflict_requires_the_version_conflict_prefix() {
+        let message = "unexpected shape: expected 3 fields, found 5";
+        assert_eq!(parse_version_conflict(message), None);
+    }
+
+    #[test]
+    fn list_aggregates_options_accepts_discovery_aliases() {
+        let value = serde_json::json!({ "prefix": "acct-", "start": "cur-1", "limit": 25 });
+        let opts = parse_list_aggregates_options(None, &value);
+        assert_eq!(opts.filter.as_deref(), Some("aggregate_id BEGINS WITH \"acct-\""));
+        assert_eq!(opts.cursor.as_deref(), Some("cur-1"));
+        assert_eq!(opts.take, Some(25));
+    }
+
+    #[test]
+    fn list_aggregates_options_escapes_quotes_in_prefix() {
+        let value = serde_json::json!({
+            "prefix": "acct-\" OR archived = true OR aggregate_id BEGINS WITH \""
+        });
+        let opts = parse_list_aggregates_options(None, &value);
+        assert_eq!(
+            opts.filter.as_deref(),
+            Some(
+                "aggregate_id BEGINS WITH \"acct-\\\" OR archived = true \
+                 OR aggregate_id BEGINS WITH \\\"\""
+            )
+        );
+    }
+
+    #[test]
+    fn list_aggregates_options_prefers_explicit_fields_over_aliases() {
+        let value = serde_json::json!({
+            "filter": "archived = false",
+            "cursor": "cur-2",
+            "take": 10,
+            "prefix": "ignored-",
+            "start": "ignored-cur",
+            "limit": 999
+        });
+        let opts = parse_list_aggregates_options(None, &value);
+        assert_eq!(opts.filter.as_deref(), Some("archived = false"));
+        assert_eq!(opts.cursor.as_deref(), Some("cur-2"));
+        assert_eq!(opts.take, Some(10));
+    }
+
+    #[test]
+    fn caught_up_marker_is_a_single_boolean_flag() {
+        let marker = caught_up_marker();
+        assert_eq!(marker, serde_json::json!({ "caughtUp": true }));
+    }
+
+    #[test]
+    fn patch_event_request_parses_version_and_causal_context() {
+        let item = serde_json::json!({
+            "aggregateType": "user",
+            "aggregateId": "u-1",
+            "eventType": "renamed",
+            "patch": { "name": "Grace" },
+            "expectedVersion": 3,
+            "causalContext": "ctx-1"
+        });
+        let request = parse_patch_event_request(&item).expect("valid item");
+        assert_eq!(request.expected_version, Some(3));
+        assert_eq!(request.causal_context.as_deref(), Some("ctx-1"));
+    }
 }
 fn clear_error(out: *mut *mut c_char) {
     if out.is_null() {
@@ -195,6 +670,90 @@ fn parse_sort(value: Option<&Value>) -> Vec<AggregateSort> {
     sorts
 }
 
+/// Escapes `\` and `"` so a caller-supplied value can be spliced into a
+/// handwritten `"..."` filter literal without the value breaking out of the
+/// quotes and injecting extra filter clauses.
+fn escape_filter_literal(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Accepts both the original `cursor`/`take`/`filter` options and the
+/// discovery-oriented aliases an admin UI reaches for first: `start` (resume
+/// point, same shape as `cursor`), `limit` (page size, same as `take`), and
+/// `prefix` (narrows to aggregate ids starting with it, still within
+/// `aggregate_type` when one is given). The explicit field always wins when
+/// both are present; a handwritten `filter` replaces the type filter.
+fn parse_list_aggregates_options(
+    aggregate_type: Option<&str>,
+    opts_value: &Value,
+) -> ListAggregatesOptions {
+    let mut opts = ListAggregatesOptions::default();
+    if let Some(map) = opts_value.as_object() {
+        if let Some(cursor) = map
+            .get("cursor")
+            .or_else(|| map.get("start"))
+            .and_then(Value::as_str)
+        {
+            opts.cursor = Some(cursor.to_string());
+        }
+        if let Some(take) = map
+            .get("take")
+            .or_else(|| map.get("limit"))
+            .and_then(Value::as_u64)
+        {
+            opts.take = Some(take);
+        }
+        if let Some(filter) = map.get("filter").and_then(Value::as_str) {
+            opts.filter = Some(filter.to_string());
+        } else if let Some(prefix) = map.get("prefix").and_then(Value::as_str) {
+            let prefix_clause = format!(
+                "aggregate_id BEGINS WITH \"{}\"",
+                escape_filter_literal(prefix)
+            );
+            opts.filter = Some(match aggregate_type {
+                Some(agg_type) => format!(
+                    "aggregate_type = \"{}\" AND {prefix_clause}",
+                    escape_filter_literal(agg_type)
+                ),
+                None => prefix_clause,
+            });
+        }
+        opts.include_archived = map
+            .get("includeArchived")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        opts.archived_only = map
+            .get("archivedOnly")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        opts.token = map.get("token").and_then(Value::as_str).map(|s| s.to_string());
+        opts.sort = parse_sort(map.get("sort"));
+    }
+    if let Some(agg_type) = aggregate_type {
+        if opts.filter.is_none() {
+            opts.filter = Some(format!("aggregate_type = \"{agg_type}\""));
+        }
+    }
+    opts
+}
+
+fn parse_list_events_options(opts_value: &Value) -> ListEventsOptions {
+    let mut opts = ListEventsOptions::default();
+    if let Some(map) = opts_value.as_object() {
+        if let Some(cursor) = map.get("cursor").and_then(Value::as_str) {
+            opts.cursor = Some(cursor.to_string());
+        }
+        if let Some(take) = map.get("take").and_then(Value::as_u64) {
+            opts.take = Some(take);
+        }
+        if let Some(filter) = map.get("filter").and_then(Value::as_str) {
+            opts.filter = Some(filter.to_string());
+        }
+        opts.token = map.get("token").and_then(Value::as_str).map(|s| s.to_string());
+    }
+    opts
+}
+
 fn parse_publish_targets(value: Option<&Value>) -> Vec<PublishTarget> {
     let mut targets = Vec::new();
     if let Some(Value::Array(items)) = value {
@@ -251,6 +810,13 @@ pub extern "C" fn dbx_client_new(
             return std::ptr::null_mut();
         }
     };
+    let cfg = match resolve_profile(cfg) {
+        Ok(cfg) => cfg,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
 
     let host = default_host(&cfg);
     let token = match default_token(&cfg) {
@@ -292,7 +858,13 @@ pub extern "C" fn dbx_client_new(
         }
     };
 
-    Box::into_raw(Box::new(DbxHandle { runtime, client }))
+    Box::into_raw(Box::new(DbxHandle {
+        inner: Arc::new(DbxClientInner {
+            runtime,
+            client,
+            stats: Mutex::new(HashMap::new()),
+        }),
+    }))
 }
 
 #[no_mangle]
@@ -305,6 +877,44 @@ pub extern "C" fn dbx_client_free(handle: *mut DbxHandle) {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn dbx_client_stats(
+    handle: *mut DbxHandle,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let client = unsafe { &mut *handle };
+    let stats = client.stats.lock().unwrap_or_else(|e| e.into_inner());
+    let payload = Value::Object(
+        stats
+            .iter()
+            .map(|(name, metric)| (name.to_string(), metric.to_json()))
+            .collect(),
+    );
+
+    match to_cstring(payload) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_client_stats_reset(handle: *mut DbxHandle) {
+    if handle.is_null() {
+        return;
+    }
+    let client = unsafe { &mut *handle };
+    let mut stats = client.stats.lock().unwrap_or_else(|e| e.into_inner());
+    stats.clear();
+}
+
 #[no_mangle]
 pub extern "C" fn dbx_list_aggregates(
     handle: *mut DbxHandle,
@@ -332,39 +942,12 @@ pub extern "C" fn dbx_list_aggregates(
             return std::ptr::null_mut();
         }
     };
-    let mut opts = ListAggregatesOptions::default();
-    if let Some(map) = opts_value.as_object() {
-        if let Some(cursor) = map.get("cursor").and_then(Value::as_str) {
-            opts.cursor = Some(cursor.to_string());
-        }
-        if let Some(take) = map.get("take").and_then(Value::as_u64) {
-            opts.take = Some(take);
-        }
-        if let Some(filter) = map.get("filter").and_then(Value::as_str) {
-            opts.filter = Some(filter.to_string());
-        }
-        opts.include_archived = map
-            .get("includeArchived")
-            .and_then(Value::as_bool)
-            .unwrap_or(false);
-        opts.archived_only = map
-            .get("archivedOnly")
-            .and_then(Value::as_bool)
-            .unwrap_or(false);
-        opts.token = map.get("token").and_then(Value::as_str).map(|s| s.to_string());
-        opts.sort = parse_sort(map.get("sort"));
-    }
-    if let Some(agg_type) = agg_type {
-        if opts.filter.is_none() {
-            opts.filter = Some(format!("aggregate_type = \"{agg_type}\""));
-        }
-    }
+    let opts = parse_list_aggregates_options(agg_type.as_deref(), &opts_value);
 
     let client = unsafe { &mut *handle };
-    let response = match client
-        .runtime
-        .block_on(client.client.list_aggregates(opts))
-    {
+    let response = match timed_operation(client, "list_aggregates", || {
+        client.runtime.block_on(client.client.list_aggregates(opts))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -396,11 +979,104 @@ pub extern "C" fn dbx_list_aggregates(
     }
 }
 
+/// Counts every event under one aggregate by walking `list_events` to
+/// exhaustion. The list endpoint only hands back one page at a time, so the
+/// total is the sum across every page rather than whatever fits in the
+/// first one. Only reached when a caller opts into `includeEventCount`,
+/// since it costs a full history walk per aggregate in the page.
+fn count_aggregate_events(
+    client: &DbxHandle,
+    aggregate_type: &str,
+    aggregate_id: &str,
+) -> Result<u64, String> {
+    let mut options = ListEventsOptions::default();
+    let mut total = 0u64;
+    loop {
+        let response = timed_operation(client, "list_events", || {
+            client.runtime.block_on(client.client.list_events(
+                aggregate_type,
+                aggregate_id,
+                options.clone(),
+            ))
+        })
+        .map_err(|err| err.to_string())?;
+        total += match &response.events {
+            Value::Array(items) => items.len() as u64,
+            Value::Null => 0,
+            _ => 1,
+        };
+        match response.next_cursor {
+            Some(cursor) => options.cursor = Some(cursor),
+            None => break,
+        }
+    }
+    Ok(total)
+}
+
+/// Projects one raw aggregate record from `list_aggregates` into the
+/// `{aggregateId, eventCount, archived}` discovery-index summary.
+/// `eventCount` stays `null` unless `include_event_count` is set: computing
+/// it walks that aggregate's full event history, so a caller only pays for
+/// it when they ask. With it set, an aggregate whose type can't be
+/// determined is an error rather than a fabricated `0`.
+fn summarize_aggregate(
+    client: &DbxHandle,
+    aggregate_type: Option<&str>,
+    item: &Value,
+    include_event_count: bool,
+) -> Result<Value, String> {
+    let obj = item.as_object();
+    let aggregate_id = obj
+        .and_then(|m| m.get("aggregateId").or_else(|| m.get("aggregate_id")))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let archived = obj
+        .and_then(|m| m.get("archived"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let event_count = if include_event_count {
+        let item_type = aggregate_type.map(str::to_string).or_else(|| {
+            obj.and_then(|m| m.get("aggregateType").or_else(|| m.get("aggregate_type")))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+        let agg_type = item_type.ok_or_else(|| {
+            format!(
+                "cannot count events for aggregate \"{aggregate_id}\": aggregateType is unknown"
+            )
+        })?;
+        Value::from(count_aggregate_events(client, &agg_type, aggregate_id)?)
+    } else {
+        Value::Null
+    };
+
+    Ok(Value::Object(
+        [
+            (
+                "aggregateId".to_string(),
+                Value::String(aggregate_id.to_string()),
+            ),
+            ("eventCount".to_string(), event_count),
+            ("archived".to_string(), Value::Bool(archived)),
+        ]
+        .into_iter()
+        .collect(),
+    ))
+}
+
+/// The lightweight "read index" counterpart to the per-item aggregate
+/// operations: pages through aggregates under a type and summarizes each
+/// one to `{aggregateId, eventCount, archived}`, so a PHP admin UI can
+/// enumerate and page through aggregates without knowing their ids in
+/// advance. `eventCount` is left `null` unless `includeEventCount` is set
+/// in `options_json`, since computing it costs a full `list_events` walk
+/// per aggregate in the page. Accepts the same `{prefix?, start?, limit?,
+/// includeArchived?}` options as `dbx_list_aggregates`.
 #[no_mangle]
-pub extern "C" fn dbx_get_aggregate(
+pub extern "C" fn dbx_list_aggregate_index(
     handle: *mut DbxHandle,
     aggregate_type: *const c_char,
-    aggregate_id: *const c_char,
+    options_json: *const c_char,
     error_out: *mut *mut c_char,
 ) -> *mut c_char {
     clear_error(error_out);
@@ -409,14 +1085,97 @@ pub extern "C" fn dbx_get_aggregate(
         return std::ptr::null_mut();
     }
     let agg_type = match string_from_ptr(aggregate_type) {
-        Ok(s) => s,
+        Ok(s) if s.is_empty() => None,
+        Ok(s) => Some(s),
         Err(err) => {
             set_error(error_out, err);
             return std::ptr::null_mut();
         }
     };
-    let agg_id = match string_from_ptr(aggregate_id) {
-        Ok(s) => s,
+    let opts_value = match parse_json(options_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let include_event_count = opts_value
+        .get("includeEventCount")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let opts = parse_list_aggregates_options(agg_type.as_deref(), &opts_value);
+
+    let client = unsafe { &mut *handle };
+    let response = match timed_operation(client, "list_aggregate_index", || {
+        client.runtime.block_on(client.client.list_aggregates(opts))
+    }) {
+        Ok(resp) => resp,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let raw_items = match response.aggregates {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    let mut aggregates = Vec::with_capacity(raw_items.len());
+    for item in &raw_items {
+        match summarize_aggregate(client, agg_type.as_deref(), item, include_event_count) {
+            Ok(summary) => aggregates.push(summary),
+            Err(err) => {
+                set_error(error_out, err);
+                return std::ptr::null_mut();
+            }
+        }
+    }
+
+    let payload = Value::Object(
+        [
+            ("aggregates".to_string(), Value::Array(aggregates)),
+            (
+                "nextCursor".to_string(),
+                response
+                    .next_cursor
+                    .map(Value::String)
+                    .unwrap_or(Value::Null),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    match to_cstring(payload) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_get_aggregate(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    aggregate_id: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let agg_id = match string_from_ptr(aggregate_id) {
+        Ok(s) => s,
         Err(err) => {
             set_error(error_out, err);
             return std::ptr::null_mut();
@@ -424,7 +1183,11 @@ pub extern "C" fn dbx_get_aggregate(
     };
 
     let client = unsafe { &mut *handle };
-    let response = match client.runtime.block_on(client.client.get_aggregate(&agg_type, &agg_id)) {
+    let response = match timed_operation(client, "get_aggregate", || {
+        client
+            .runtime
+            .block_on(client.client.get_aggregate(&agg_type, &agg_id))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -501,10 +1264,9 @@ pub extern "C" fn dbx_select_aggregate(
 
     let request = SelectAggregateRequest::new(agg_type, agg_id, fields);
     let client = unsafe { &mut *handle };
-    let response = match client
-        .runtime
-        .block_on(client.client.select_aggregate(request))
-    {
+    let response = match timed_operation(client, "select_aggregate", || {
+        client.runtime.block_on(client.client.select_aggregate(request))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -567,25 +1329,12 @@ pub extern "C" fn dbx_list_events(
             return std::ptr::null_mut();
         }
     };
-    let mut opts = ListEventsOptions::default();
-    if let Some(map) = opts_value.as_object() {
-        if let Some(cursor) = map.get("cursor").and_then(Value::as_str) {
-            opts.cursor = Some(cursor.to_string());
-        }
-        if let Some(take) = map.get("take").and_then(Value::as_u64) {
-            opts.take = Some(take);
-        }
-        if let Some(filter) = map.get("filter").and_then(Value::as_str) {
-            opts.filter = Some(filter.to_string());
-        }
-        opts.token = map.get("token").and_then(Value::as_str).map(|s| s.to_string());
-    }
+    let opts = parse_list_events_options(&opts_value);
 
     let client = unsafe { &mut *handle };
-    let response = match client
-        .runtime
-        .block_on(client.client.list_events(&agg_type, &agg_id, opts))
-    {
+    let response = match timed_operation(client, "list_events", || {
+        client.runtime.block_on(client.client.list_events(&agg_type, &agg_id, opts))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -616,14 +1365,25 @@ pub extern "C" fn dbx_list_events(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn parse_payload_options(
     opts_value: Value,
-) -> (Value, Option<String>, Option<Value>, Option<String>, Vec<PublishTarget>) {
+) -> (
+    Value,
+    Option<String>,
+    Option<Value>,
+    Option<String>,
+    Vec<PublishTarget>,
+    Option<u64>,
+    Option<String>,
+) {
     let mut payload = Value::Null;
     let mut metadata = None;
     let mut note = None;
     let mut token = None;
     let mut publish_targets = Vec::new();
+    let mut expected_version = None;
+    let mut causal_context = None;
 
     if let Some(map) = opts_value.as_object() {
         if let Some(p) = map.get("payload") {
@@ -633,9 +1393,22 @@ fn parse_payload_options(
         note = map.get("note").and_then(Value::as_str).map(|s| s.to_string());
         token = map.get("token").and_then(Value::as_str).map(|s| s.to_string());
         publish_targets = parse_publish_targets(map.get("publishTargets"));
+        expected_version = map.get("expectedVersion").and_then(Value::as_u64);
+        causal_context = map
+            .get("causalContext")
+            .and_then(Value::as_str)
+            .map(|s| s.to_string());
     }
 
-    (payload, note, metadata, token, publish_targets)
+    (
+        payload,
+        note,
+        metadata,
+        token,
+        publish_targets,
+        expected_version,
+        causal_context,
+    )
 }
 
 #[no_mangle]
@@ -681,7 +1454,7 @@ pub extern "C" fn dbx_append_event(
         }
     };
 
-    let (payload, note, metadata, token, publish_targets) = parse_payload_options(opts_value);
+    let (payload, note, metadata, token, publish_targets, _, _) = parse_payload_options(opts_value);
     let payload = match payload {
         Value::Null => Value::Object(Map::new()),
         other => other,
@@ -694,7 +1467,9 @@ pub extern "C" fn dbx_append_event(
     request.publish_targets = publish_targets;
 
     let client = unsafe { &mut *handle };
-    let response = match client.runtime.block_on(client.client.append_event(request)) {
+    let response = match timed_operation(client, "append_event", || {
+        client.runtime.block_on(client.client.append_event(request))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -759,7 +1534,7 @@ pub extern "C" fn dbx_create_aggregate(
         }
     };
 
-    let (payload, note, metadata, token, publish_targets) = parse_payload_options(opts_value);
+    let (payload, note, metadata, token, publish_targets, _, _) = parse_payload_options(opts_value);
     let payload = match payload {
         Value::Null => Value::Object(Map::new()),
         other => other,
@@ -772,7 +1547,9 @@ pub extern "C" fn dbx_create_aggregate(
     request.publish_targets = publish_targets;
 
     let client = unsafe { &mut *handle };
-    let response = match client.runtime.block_on(client.client.create_aggregate(request)) {
+    let response = match timed_operation(client, "create_aggregate", || {
+        client.runtime.block_on(client.client.create_aggregate(request))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
             set_error(error_out, err.to_string());
@@ -794,6 +1571,29 @@ pub extern "C" fn dbx_create_aggregate(
     }
 }
 
+/// Recognizes the server's `"version conflict: expected N, found M"` error
+/// text and pulls out the two versions so a caller can rebase and retry
+/// instead of just seeing an opaque string. Anchored to the `"version
+/// conflict"` prefix so an unrelated error that merely contains the words
+/// "expected"/"found" (e.g. a validation message) isn't misclassified as a
+/// conflict with fabricated version numbers.
+fn parse_version_conflict(message: &str) -> Option<(u64, u64)> {
+    let rest = message.strip_prefix("version conflict")?;
+    let rest = rest.split_once("expected ")?.1;
+    let (expected, rest) = rest.split_once(", found ")?;
+    let found: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    Some((expected.trim().parse().ok()?, found.parse().ok()?))
+}
+
+/// `options_json` may carry an `expectedVersion` and/or `causalContext` for
+/// optimistic concurrency: the server rejects the patch with a version
+/// conflict error if the aggregate has moved on since the caller last read
+/// it, instead of silently applying the patch over a concurrent writer. A
+/// conflict is surfaced through `error_out` as a JSON object (`{"error":
+/// "version_conflict", "expectedVersion": N, "currentVersion": M, "message":
+/// ...}`) rather than the plain error text every other failure uses, so a
+/// caller can distinguish it from a generic error and read back the current
+/// version to retry against.
 #[no_mangle]
 pub extern "C" fn dbx_patch_event(
     handle: *mut DbxHandle,
@@ -846,19 +1646,46 @@ pub extern "C" fn dbx_patch_event(
             return std::ptr::null_mut();
         }
     };
-    let (_, note, metadata, token, publish_targets) = parse_payload_options(opts_value);
+    let (_, note, metadata, token, publish_targets, expected_version, causal_context) =
+        parse_payload_options(opts_value);
 
     let mut request = PatchEventRequest::new(agg_type, agg_id, evt_type, patch_value);
     request.note = note;
     request.metadata = metadata;
     request.token = token;
     request.publish_targets = publish_targets;
+    request.expected_version = expected_version;
+    request.causal_context = causal_context;
 
     let client = unsafe { &mut *handle };
-    let response = match client.runtime.block_on(client.client.patch_event(request)) {
+    let response = match timed_operation(client, "patch_event", || {
+        client.runtime.block_on(client.client.patch_event(request))
+    }) {
         Ok(resp) => resp,
         Err(err) => {
-            set_error(error_out, err.to_string());
+            let message = err.to_string();
+            match parse_version_conflict(&message) {
+                Some((expected_version, current_version)) => {
+                    let conflict = Value::Object(
+                        [
+                            (
+                                "error".to_string(),
+                                Value::String("version_conflict".to_string()),
+                            ),
+                            ("message".to_string(), Value::String(message)),
+                            ("expectedVersion".to_string(), Value::from(expected_version)),
+                            ("currentVersion".to_string(), Value::from(current_version)),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    );
+                    set_error(
+                        error_out,
+                        serde_json::to_string(&conflict).unwrap_or(message),
+                    );
+                }
+                None => set_error(error_out, message),
+            }
             return std::ptr::null_mut();
         }
     };
@@ -945,6 +1772,268 @@ pub extern "C" fn dbx_set_archive(
     }
 }
 
+fn parse_append_event_request(item: &Value) -> Result<AppendEventRequest, String> {
+    let agg_type = item
+        .get("aggregateType")
+        .and_then(Value::as_str)
+        .ok_or("aggregateType is required")?
+        .to_string();
+    let agg_id = item
+        .get("aggregateId")
+        .and_then(Value::as_str)
+        .ok_or("aggregateId is required")?
+        .to_string();
+    let evt_type = item
+        .get("eventType")
+        .and_then(Value::as_str)
+        .ok_or("eventType is required")?
+        .to_string();
+
+    let (payload, note, metadata, token, publish_targets, _, _) =
+        parse_payload_options(item.clone());
+    let payload = match payload {
+        Value::Null => Value::Object(Map::new()),
+        other => other,
+    };
+
+    let mut request = AppendEventRequest::new(agg_type, agg_id, evt_type, payload);
+    request.note = note;
+    request.metadata = metadata;
+    request.token = token;
+    request.publish_targets = publish_targets;
+    Ok(request)
+}
+
+fn parse_patch_event_request(item: &Value) -> Result<PatchEventRequest, String> {
+    let agg_type = item
+        .get("aggregateType")
+        .and_then(Value::as_str)
+        .ok_or("aggregateType is required")?
+        .to_string();
+    let agg_id = item
+        .get("aggregateId")
+        .and_then(Value::as_str)
+        .ok_or("aggregateId is required")?
+        .to_string();
+    let evt_type = item
+        .get("eventType")
+        .and_then(Value::as_str)
+        .ok_or("eventType is required")?
+        .to_string();
+    let patch = item.get("patch").cloned().ok_or("patch is required")?;
+
+    let (_, note, metadata, token, publish_targets, expected_version, causal_context) =
+        parse_payload_options(item.clone());
+
+    let mut request = PatchEventRequest::new(agg_type, agg_id, evt_type, patch);
+    request.note = note;
+    request.metadata = metadata;
+    request.token = token;
+    request.publish_targets = publish_targets;
+    request.expected_version = expected_version;
+    request.causal_context = causal_context;
+    Ok(request)
+}
+
+/// Submits a batch of patches in one server request, falling back to a
+/// pipelined loop of individual `patch_event` calls when the server has no
+/// dedicated batch route. Returns a JSON array of per-item results
+/// (`{"event": ...}` or `{"error": "..."}`) so one bad patch doesn't fail the
+/// whole batch; pass `{"atomic": true}` in `options_json` to instead ask the
+/// server to apply every patch in one transaction or none. Items are checked
+/// before anything is sent: an item missing a required field fails the whole
+/// call through `error_out` and no patch is submitted.
+#[no_mangle]
+pub extern "C" fn dbx_patch_events_batch(
+    handle: *mut DbxHandle,
+    patches_json: *const c_char,
+    options_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+
+    let patches_value = match parse_json(patches_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let items = match patches_value {
+        Value::Array(items) => items,
+        _ => {
+            set_error(error_out, "patches must be a JSON array");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let opts_value = match parse_json(options_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let atomic = opts_value
+        .as_object()
+        .and_then(|map| map.get("atomic"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut requests = Vec::with_capacity(items.len());
+    for item in &items {
+        match parse_patch_event_request(item) {
+            Ok(request) => requests.push(request),
+            Err(err) => {
+                set_error(error_out, err);
+                return std::ptr::null_mut();
+            }
+        }
+    }
+
+    let client = unsafe { &mut *handle };
+    let outcome = client
+        .runtime
+        .block_on(client.client.patch_events_batch(requests, atomic));
+
+    let results = match outcome {
+        Ok(events) => events,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let items: Vec<Value> = results
+        .into_iter()
+        .map(|result| match result {
+            Ok(event) => Value::Object([("event".to_string(), event)].into_iter().collect()),
+            Err(err) => Value::Object(
+                [("error".to_string(), Value::String(err))]
+                    .into_iter()
+                    .collect(),
+            ),
+        })
+        .collect();
+
+    match to_cstring(Value::Array(items)) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_append_events_batch(
+    handle: *mut DbxHandle,
+    events_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+
+    let events_value = match parse_json(events_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let (items, atomic) = match &events_value {
+        Value::Array(items) => (items.clone(), false),
+        Value::Object(map) => {
+            let items = map
+                .get("events")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let atomic = map.get("atomic").and_then(Value::as_bool).unwrap_or(false);
+            (items, atomic)
+        }
+        _ => {
+            set_error(error_out, "events must be a JSON array");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut requests = Vec::with_capacity(items.len());
+    for item in &items {
+        match parse_append_event_request(item) {
+            Ok(request) => requests.push(request),
+            Err(err) => {
+                set_error(error_out, err);
+                return std::ptr::null_mut();
+            }
+        }
+    }
+
+    let client = unsafe { &mut *handle };
+    let outcome = client
+        .runtime
+        .block_on(client.client.append_events_batch(requests, atomic));
+
+    let results = match outcome {
+        Ok(events) => events,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut appended = 0u64;
+    let items: Vec<Value> = results
+        .into_iter()
+        .map(|result| match result {
+            Ok(event) => {
+                appended += 1;
+                Value::Object(
+                    [
+                        ("ok".to_string(), Value::Bool(true)),
+                        ("event".to_string(), event),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )
+            }
+            Err(err) => Value::Object(
+                [
+                    ("ok".to_string(), Value::Bool(false)),
+                    ("error".to_string(), Value::String(err)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        })
+        .collect();
+
+    let payload = Value::Object(
+        [
+            ("results".to_string(), Value::Array(items)),
+            ("appended".to_string(), Value::Number(appended.into())),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    match to_cstring(payload) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn dbx_verify_aggregate(
     handle: *mut DbxHandle,
@@ -998,3 +2087,645 @@ pub extern "C" fn dbx_verify_aggregate(
         }
     }
 }
+
+fn parse_follow_filter(value: Option<&Value>) -> FollowEventsFilter {
+    let mut filter = FollowEventsFilter::default();
+    if let Some(map) = value.and_then(Value::as_object) {
+        if let Some(cursor) = map.get("fromCursor").and_then(Value::as_str) {
+            filter.from_cursor = Some(cursor.to_string());
+        }
+        if let Some(raw_filter) = map.get("filter").and_then(Value::as_str) {
+            filter.filter = Some(raw_filter.to_string());
+        }
+    }
+    filter
+}
+
+/// Opens a live subscription on `aggregate_type`, invoking `callback` on the
+/// runtime thread for each delivered event. The `*const c_char` passed to the
+/// callback is only valid for the duration of the call; the callback must
+/// copy it if it needs to outlive that invocation. Pass `fromCursor` in
+/// `filter_json` to resume a previously-stopped subscription.
+#[no_mangle]
+pub extern "C" fn dbx_subscribe(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    filter_json: *const c_char,
+    callback: extern "C" fn(*const c_char, *mut c_void),
+    user_data: *mut c_void,
+    error_out: *mut *mut c_char,
+) -> *mut DbxSubscription {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let filter_value = match parse_json(filter_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let filter = parse_follow_filter(Some(&filter_value));
+
+    let inner = unsafe { &*handle }.inner.clone();
+    let mut stream = match inner.runtime.block_on(inner.client.follow_events(&agg_type, filter)) {
+        Ok(stream) => stream,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task_cancelled = cancelled.clone();
+    let callback = SubscriptionCallback { callback, user_data };
+    let runtime_handle = inner.runtime.handle().clone();
+
+    let task = runtime_handle.spawn(async move {
+        let callback = callback;
+        while !task_cancelled.load(Ordering::Acquire) {
+            match stream.next().await {
+                Some(Ok(event)) => {
+                    let text = match serde_json::to_string(&event) {
+                        Ok(text) => text,
+                        Err(_) => continue,
+                    };
+                    if let Ok(cstr) = CString::new(text) {
+                        (callback.callback)(cstr.as_ptr(), callback.user_data);
+                    }
+                }
+                Some(Err(_)) | None => break,
+            }
+        }
+    });
+
+    Box::into_raw(Box::new(DbxSubscription {
+        inner,
+        cancelled,
+        runtime_handle,
+        task: Some(task),
+    }))
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_events_cursor_open(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    aggregate_id: *const c_char,
+    options_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut DbxCursor {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let agg_id = match string_from_ptr(aggregate_id) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let opts_value = match parse_json(options_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let options = parse_list_events_options(&opts_value);
+    let inner = unsafe { &*handle }.inner.clone();
+
+    Box::into_raw(Box::new(DbxCursor {
+        inner,
+        query: CursorQuery::Events {
+            aggregate_type: agg_type,
+            aggregate_id: agg_id,
+            options,
+        },
+        exhausted: false,
+    }))
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_aggregates_cursor_open(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    options_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut DbxCursor {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) if s.is_empty() => None,
+        Ok(s) => Some(s),
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let opts_value = match parse_json(options_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let options = parse_list_aggregates_options(agg_type.as_deref(), &opts_value);
+    let inner = unsafe { &*handle }.inner.clone();
+
+    Box::into_raw(Box::new(DbxCursor {
+        inner,
+        query: CursorQuery::Aggregates { options },
+        exhausted: false,
+    }))
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_cursor_next(
+    cursor: *mut DbxCursor,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if cursor.is_null() {
+        set_error(error_out, "cursor is null");
+        return std::ptr::null_mut();
+    }
+    let cursor = unsafe { &mut *cursor };
+    if cursor.exhausted {
+        return to_cstring(Value::Array(Vec::new())).unwrap_or_else(|_| std::ptr::null_mut());
+    }
+    let client = cursor.inner.as_ref();
+
+    let (items, next_cursor) = match &mut cursor.query {
+        CursorQuery::Events {
+            aggregate_type,
+            aggregate_id,
+            options,
+        } => {
+            let response = match client.runtime.block_on(client.client.list_events(
+                aggregate_type,
+                aggregate_id,
+                options.clone(),
+            )) {
+                Ok(resp) => resp,
+                Err(err) => {
+                    set_error(error_out, err.to_string());
+                    return std::ptr::null_mut();
+                }
+            };
+            let items = match response.events {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            (items, response.next_cursor)
+        }
+        CursorQuery::Aggregates { options } => {
+            let response = match client
+                .runtime
+                .block_on(client.client.list_aggregates(options.clone()))
+            {
+                Ok(resp) => resp,
+                Err(err) => {
+                    set_error(error_out, err.to_string());
+                    return std::ptr::null_mut();
+                }
+            };
+            let items = match response.aggregates {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            (items, response.next_cursor)
+        }
+    };
+
+    match &next_cursor {
+        Some(cursor_value) => match &mut cursor.query {
+            CursorQuery::Events { options, .. } => options.cursor = Some(cursor_value.clone()),
+            CursorQuery::Aggregates { options } => options.cursor = Some(cursor_value.clone()),
+        },
+        None => cursor.exhausted = true,
+    }
+
+    match to_cstring(Value::Array(items)) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_cursor_free(cursor: *mut DbxCursor) {
+    if cursor.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(cursor));
+    }
+}
+
+/// Tears down a subscription opened by `dbx_subscribe`. Normally joins the
+/// aborted background task so the caller knows the `callback` has truly
+/// stopped firing before this returns. A callback that unsubscribes itself
+/// (a natural "stop after this event" pattern) runs synchronously on this
+/// runtime's own worker thread, inside the very task being joined below —
+/// `block_on` from there would panic ("Cannot start a runtime from within a
+/// runtime"). `Handle::try_current()` detects that reentrant case (it's
+/// `Ok` only while already executing inside a Tokio runtime context), and
+/// we skip the join in favor of the `abort()` call alone, which is still
+/// enough to stop future callback invocations.
+#[no_mangle]
+pub extern "C" fn dbx_unsubscribe(subscription: *mut DbxSubscription) {
+    if subscription.is_null() {
+        return;
+    }
+    let mut subscription = unsafe { Box::from_raw(subscription) };
+    subscription.cancelled.store(true, Ordering::Release);
+    if let Some(task) = subscription.task.take() {
+        task.abort();
+        if tokio::runtime::Handle::try_current().is_err() {
+            let _ = subscription.runtime_handle.block_on(async move {
+                let _ = task.await;
+            });
+        }
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hex_decode(text: &str) -> Result<Vec<u8>, String> {
+    if text.len() % 2 != 0 {
+        return Err(format!("invalid hex: odd length ({text})"));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|e| format!("invalid hex: {e}")))
+        .collect()
+}
+
+/// Serializes `value` with object keys sorted recursively so the same event
+/// always hashes to the same leaf bytes regardless of map iteration order.
+fn canonical_event_bytes(value: &Value) -> Vec<u8> {
+    fn canonicalize(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut sorted: Vec<_> = map.iter().collect();
+                sorted.sort_by(|a, b| a.0.cmp(b.0));
+                Value::Object(
+                    sorted
+                        .into_iter()
+                        .map(|(k, v)| (k.clone(), canonicalize(v)))
+                        .collect(),
+                )
+            }
+            Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+            other => other.clone(),
+        }
+    }
+    serde_json::to_vec(&canonicalize(value)).unwrap_or_default()
+}
+
+struct ProofStep {
+    sibling: Vec<u8>,
+    is_right: bool,
+}
+
+fn parse_inclusion_proof(value: &Value) -> Result<Vec<ProofStep>, String> {
+    let items = value.as_array().ok_or("proof must be a JSON array")?;
+    items
+        .iter()
+        .map(|item| {
+            let sibling_hex = item
+                .get("sibling")
+                .and_then(Value::as_str)
+                .ok_or("proof step is missing \"sibling\"")?;
+            let is_right = item
+                .get("isRight")
+                .and_then(Value::as_bool)
+                .ok_or("proof step is missing \"isRight\"")?;
+            Ok(ProofStep {
+                sibling: hex_decode(sibling_hex)?,
+                is_right,
+            })
+        })
+        .collect()
+}
+
+/// Recomputes the Merkle root for `leaf` given its inclusion `proof`, using a
+/// domain-separated SHA-256 (`0x00` for leaves, `0x01` for internal nodes) to
+/// prevent leaf/internal-node second-preimage confusion.
+fn compute_merkle_root(leaf: &Value, proof: &[ProofStep]) -> String {
+    let mut current: Vec<u8> = {
+        let mut hasher = Sha256::new();
+        hasher.update([0x00]);
+        hasher.update(canonical_event_bytes(leaf));
+        hasher.finalize().to_vec()
+    };
+    for step in proof {
+        let mut hasher = Sha256::new();
+        hasher.update([0x01]);
+        if step.is_right {
+            hasher.update(&current);
+            hasher.update(&step.sibling);
+        } else {
+            hasher.update(&step.sibling);
+            hasher.update(&current);
+        }
+        current = hasher.finalize().to_vec();
+    }
+    hex_encode(&current)
+}
+
+/// Fetches the server's inclusion proof for a single event and verifies it
+/// locally against the aggregate's current Merkle root, so callers get a
+/// trustworthy `verified` boolean instead of trusting the server's word. A
+/// recomputed root that differs from the aggregate's root is reported through
+/// `error_out`, so a returned payload always has `verified: true`.
+#[no_mangle]
+pub extern "C" fn dbx_verify_event(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    aggregate_id: *const c_char,
+    event_id: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let agg_id = match string_from_ptr(aggregate_id) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let evt_id = match string_from_ptr(event_id) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let client = unsafe { &mut *handle };
+
+    let EventInclusionProof { leaf, proof } = match client.runtime.block_on(
+        client.client.event_inclusion_proof(&agg_type, &agg_id, &evt_id),
+    ) {
+        Ok(proof) => proof,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let steps = match parse_inclusion_proof(&proof) {
+        Ok(steps) => steps,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let verify_response = match client
+        .runtime
+        .block_on(client.client.verify_aggregate(&agg_type, &agg_id))
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            set_error(error_out, err.to_string());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let computed_root = compute_merkle_root(&leaf, &steps);
+    if computed_root != verify_response.merkle_root {
+        set_error(
+            error_out,
+            format!(
+                "merkle proof mismatch: computed {computed_root}, expected {}",
+                verify_response.merkle_root
+            ),
+        );
+        return std::ptr::null_mut();
+    }
+
+    let payload = Value::Object(
+        [
+            ("verified".to_string(), Value::Bool(true)),
+            ("merkleRoot".to_string(), Value::String(verify_response.merkle_root)),
+            ("proof".to_string(), proof),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    match to_cstring(payload) {
+        Ok(ptr) => ptr,
+        Err(err) => {
+            set_error(error_out, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+const SUBSCRIBE_BACKOFF_INITIAL: Duration = Duration::from_millis(100);
+const SUBSCRIBE_BACKOFF_MAX: Duration = Duration::from_secs(5);
+
+fn caught_up_marker() -> Value {
+    Value::Object(
+        [("caughtUp".to_string(), Value::Bool(true))]
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// Opens a resumable, reconnecting subscription on one aggregate. Historical
+/// events are replayed from `cursor_json`'s `fromCursor` (if any), then a
+/// `{"caughtUp": true}` sentinel is delivered once the backlog drains, after
+/// which the stream tails new events live. Transport errors trigger a
+/// transparent reconnect from the last delivered cursor with capped
+/// exponential backoff rather than surfacing every blip to the caller.
+#[no_mangle]
+pub extern "C" fn dbx_subscribe_open(
+    handle: *mut DbxHandle,
+    aggregate_type: *const c_char,
+    aggregate_id: *const c_char,
+    cursor_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut DbxSubscribeStream {
+    clear_error(error_out);
+    if handle.is_null() {
+        set_error(error_out, "handle is null");
+        return std::ptr::null_mut();
+    }
+    let agg_type = match string_from_ptr(aggregate_type) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let agg_id = match string_from_ptr(aggregate_id) {
+        Ok(s) => s,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let cursor_value = match parse_json(cursor_json) {
+        Ok(v) => v,
+        Err(err) => {
+            set_error(error_out, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let mut cursor = parse_follow_filter(Some(&cursor_value)).from_cursor;
+
+    let inner = unsafe { &*handle }.inner.clone();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task_cancelled = cancelled.clone();
+    let runtime_handle = inner.runtime.handle().clone();
+    let (sender, receiver) = std::sync::mpsc::channel::<Value>();
+    let eventdbx_client = inner.client.clone();
+    let agg_id_filter = format!("aggregate_id = \"{}\"", escape_filter_literal(&agg_id));
+
+    let task = runtime_handle.spawn(async move {
+        let mut backoff = SUBSCRIBE_BACKOFF_INITIAL;
+        let mut caught_up = false;
+
+        'reconnect: while !task_cancelled.load(Ordering::Acquire) {
+            let filter = FollowEventsFilter {
+                from_cursor: cursor.clone(),
+                filter: Some(agg_id_filter.clone()),
+            };
+            let mut stream = match eventdbx_client.follow_events(&agg_type, filter).await {
+                Ok(stream) => stream,
+                Err(_) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(SUBSCRIBE_BACKOFF_MAX);
+                    continue 'reconnect;
+                }
+            };
+            backoff = SUBSCRIBE_BACKOFF_INITIAL;
+
+            loop {
+                if task_cancelled.load(Ordering::Acquire) {
+                    break 'reconnect;
+                }
+                match stream.next().await {
+                    Some(Ok(event)) => {
+                        if let Some(id) = event.get("id").and_then(Value::as_str) {
+                            cursor = Some(id.to_string());
+                        }
+                        if sender.send(event).is_err() {
+                            break 'reconnect;
+                        }
+                    }
+                    Some(Err(_)) => {
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(SUBSCRIBE_BACKOFF_MAX);
+                        continue 'reconnect;
+                    }
+                    None => {
+                        if !caught_up {
+                            caught_up = true;
+                            if sender.send(caught_up_marker()).is_err() {
+                                break 'reconnect;
+                            }
+                        }
+                        tokio::time::sleep(SUBSCRIBE_BACKOFF_INITIAL).await;
+                        continue 'reconnect;
+                    }
+                }
+            }
+        }
+    });
+
+    Box::into_raw(Box::new(DbxSubscribeStream {
+        inner,
+        receiver,
+        cancelled,
+        runtime_handle,
+        task: Some(task),
+    }))
+}
+
+/// Blocks up to `timeout_ms` for the next event, returning `null` (with no
+/// error) on timeout so the caller can poll other work and come back.
+#[no_mangle]
+pub extern "C" fn dbx_subscribe_next(
+    stream: *mut DbxSubscribeStream,
+    timeout_ms: u64,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    clear_error(error_out);
+    if stream.is_null() {
+        set_error(error_out, "stream is null");
+        return std::ptr::null_mut();
+    }
+    let stream = unsafe { &mut *stream };
+
+    match stream.receiver.recv_timeout(Duration::from_millis(timeout_ms)) {
+        Ok(event) => match to_cstring(event) {
+            Ok(ptr) => ptr,
+            Err(err) => {
+                set_error(error_out, err);
+                std::ptr::null_mut()
+            }
+        },
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => std::ptr::null_mut(),
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+            set_error(error_out, "subscription closed");
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn dbx_subscribe_close(stream: *mut DbxSubscribeStream) {
+    if stream.is_null() {
+        return;
+    }
+    let mut stream = unsafe { Box::from_raw(stream) };
+    stream.cancelled.store(true, Ordering::Release);
+    if let Some(task) = stream.task.take() {
+        task.abort();
+        let _ = stream.runtime_handle.block_on(async move {
+            let _ = task.await;
+        });
+    }
+}